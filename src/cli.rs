@@ -1,4 +1,4 @@
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
 #[derive(Parser)]
@@ -14,9 +14,12 @@ pub struct Cli {
 pub enum Commands {
     /// Create a new encrypted repository
     Create {
-        /// GPG user/email for encryption (required)
-        #[arg(short, long)]
-        user: String,
+        /// GPG user/email(s) to encrypt the passphrase to (required, may repeat)
+        #[arg(short = 'u', long = "user")]
+        recipients: Vec<String>,
+        /// Encryption cipher (see `show-ciphers` for the full list)
+        #[arg(long, value_enum, default_value_t = Cipher::AesGcm)]
+        cipher: Cipher,
         /// Target repository directory
         repo_dir: PathBuf,
     },
@@ -25,6 +28,12 @@ pub enum Commands {
         /// Options passed through to the cryptfs backend
         #[arg(short, long)]
         options: Option<String>,
+        /// Run the backend in the foreground instead of daemonizing (for systemd/containers)
+        #[arg(short = 'f', long)]
+        foreground: bool,
+        /// Redirect the backend's stdout/stderr to this file (created 0600, append mode)
+        #[arg(long)]
+        logfile: Option<PathBuf>,
         /// Repository directory (containing passphrase.gpg + objects)
         repo_dir: PathBuf,
         /// Mount point or drive letter (Windows)
@@ -35,4 +44,55 @@ pub enum Commands {
         /// Mount point or drive letter (Windows)
         mount_point: String,
     },
+    /// Show whether a repository is currently mounted, and where
+    Status {
+        /// Repository directory (containing passphrase.gpg + objects)
+        repo_dir: PathBuf,
+    },
+    /// List the encryption ciphers supported by `create --cipher`
+    ShowCiphers,
+    /// Re-encrypt a repository's passphrase to a new set of GPG recipients
+    Rekey {
+        /// Repository directory (containing passphrase.gpg)
+        repo_dir: PathBuf,
+        /// GPG recipient(s) who should be able to decrypt the passphrase (required)
+        #[arg(short = 'r', long = "recipient")]
+        recipients: Vec<String>,
+    },
+}
+
+/// Encryption cipher used for a repository's contents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, ValueEnum)]
+pub enum Cipher {
+    /// AES-256-GCM, the gocryptfs default; fast with AES-NI hardware acceleration
+    #[value(name = "aes-gcm")]
+    AesGcm,
+    /// AES-256-SIV, misuse-resistant but slower; required for reverse mode
+    #[value(name = "aes-siv")]
+    AesSiv,
+    /// XChaCha20-Poly1305; fast on hardware without AES acceleration
+    #[value(name = "xchacha")]
+    Xchacha,
+}
+
+impl Cipher {
+    /// The name used on the command line and recorded in the repository.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Cipher::AesGcm => "aes-gcm",
+            Cipher::AesSiv => "aes-siv",
+            Cipher::Xchacha => "xchacha",
+        }
+    }
+
+    /// A one-line description for `show-ciphers`.
+    pub fn description(&self) -> &'static str {
+        match self {
+            Cipher::AesGcm => {
+                "AES-256-GCM, the gocryptfs default; fast with AES-NI hardware acceleration"
+            }
+            Cipher::AesSiv => "AES-256-SIV, misuse-resistant but slower; required for reverse mode",
+            Cipher::Xchacha => "XChaCha20-Poly1305; fast on hardware without AES acceleration",
+        }
+    }
 }
@@ -1,4 +1,5 @@
 mod cli;
+mod mount_table;
 mod ops;
 mod process;
 
@@ -20,19 +21,37 @@ fn run() -> Result<()> {
     let cli = Cli::parse();
 
     match cli.command {
-        Commands::Create { user, repo_dir } => ops::create(&user, &repo_dir)?,
+        Commands::Create {
+            recipients,
+            cipher,
+            repo_dir,
+        } => ops::create(&recipients, &repo_dir, cipher)?,
         Commands::Mount {
             options,
+            foreground,
+            logfile,
             repo_dir,
             mount_point,
         } => {
             let normalized_mount = ops::normalize_mount_point(&mount_point)?;
-            ops::mount(&repo_dir, &normalized_mount, options.as_deref())?;
+            ops::mount(
+                &repo_dir,
+                &normalized_mount,
+                options.as_deref(),
+                foreground,
+                logfile.as_deref(),
+            )?;
         }
         Commands::Umount { mount_point } => {
             let normalized_mount = ops::normalize_mount_point(&mount_point)?;
             ops::umount(&normalized_mount)?;
         }
+        Commands::Status { repo_dir } => ops::status(&repo_dir)?,
+        Commands::ShowCiphers => ops::show_ciphers(),
+        Commands::Rekey {
+            repo_dir,
+            recipients,
+        } => ops::rekey(&repo_dir, &recipients)?,
     }
 
     Ok(())
@@ -0,0 +1,99 @@
+use anyhow::{Context, Result};
+use std::path::{Path, PathBuf};
+
+/// A single parsed entry from the system mount table.
+#[derive(Debug, Clone)]
+pub struct MountEntry {
+    pub source: PathBuf,
+    pub target: PathBuf,
+    pub fstype: String,
+    pub options: String,
+}
+
+/// Return every entry currently in the system mount table.
+///
+/// On Linux this parses `/proc/mounts`, splitting each line on whitespace
+/// into source/target/fstype/options and skipping malformed lines with
+/// fewer than four fields. Windows has no equivalent table, so this always
+/// returns an empty list there; use `is_target_mounted` instead, which
+/// checks drive letter resolution directly.
+#[cfg(not(target_os = "windows"))]
+pub fn all_mounts() -> Result<Vec<MountEntry>> {
+    let contents = std::fs::read_to_string("/proc/mounts").context("failed to read /proc/mounts")?;
+
+    let mut entries = Vec::new();
+    for line in contents.lines() {
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() < 4 {
+            continue;
+        }
+        entries.push(MountEntry {
+            source: decode_mount_field(fields[0]),
+            target: decode_mount_field(fields[1]),
+            fstype: fields[2].to_string(),
+            options: fields[3].to_string(),
+        });
+    }
+
+    Ok(entries)
+}
+
+/// Decode the octal escapes `/proc/mounts` uses for space (`\040`), tab
+/// (`\011`), newline (`\012`) and backslash (`\134`) in path fields.
+#[cfg(not(target_os = "windows"))]
+fn decode_mount_field(field: &str) -> PathBuf {
+    use std::ffi::OsString;
+    use std::os::unix::ffi::OsStringExt;
+
+    let bytes = field.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'\\'
+            && i + 3 < bytes.len()
+            && bytes[i + 1..i + 4].iter().all(|b| (b'0'..=b'7').contains(b))
+        {
+            let octal = std::str::from_utf8(&bytes[i + 1..i + 4]).unwrap();
+            decoded.push(u8::from_str_radix(octal, 8).unwrap());
+            i += 4;
+        } else {
+            decoded.push(bytes[i]);
+            i += 1;
+        }
+    }
+
+    PathBuf::from(OsString::from_vec(decoded))
+}
+
+#[cfg(target_os = "windows")]
+pub fn all_mounts() -> Result<Vec<MountEntry>> {
+    Ok(Vec::new())
+}
+
+/// Whether `path` appears as the source (backing directory) of a mount.
+#[cfg(not(target_os = "windows"))]
+pub fn is_source_mounted(path: &Path) -> Result<bool> {
+    let mounts = all_mounts()?;
+    Ok(mounts.iter().any(|entry| entry.source == path))
+}
+
+/// Windows has no concept of a source directory in its mount table; a
+/// cppcryptfs repository is only ever identified by its drive letter.
+#[cfg(target_os = "windows")]
+pub fn is_source_mounted(_path: &Path) -> Result<bool> {
+    Ok(false)
+}
+
+/// Whether `path` is currently mounted as a target.
+#[cfg(not(target_os = "windows"))]
+pub fn is_target_mounted(path: &Path) -> Result<bool> {
+    let mounts = all_mounts()?;
+    Ok(mounts.iter().any(|entry| entry.target == path))
+}
+
+/// On Windows a mount point is a drive letter; treat it as mounted if the
+/// drive currently resolves to a filesystem root.
+#[cfg(target_os = "windows")]
+pub fn is_target_mounted(path: &Path) -> Result<bool> {
+    Ok(path.is_dir())
+}
@@ -1,10 +1,18 @@
 use super::set_secret_mode;
-use crate::process::run_with_output;
-use anyhow::{Context, Result};
+use crate::cli::Cipher;
+use crate::process::{ensure_available, run_with_output};
+use anyhow::{bail, Context, Result};
+use std::fs::{File, OpenOptions};
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, Stdio};
 
-pub fn init_repository(repo_dir: &Path, objects_dir: &Path, passphrase_file: &Path) -> Result<()> {
+pub fn init_repository(
+    repo_dir: &Path,
+    objects_dir: &Path,
+    passphrase_file: &Path,
+    cipher: Cipher,
+) -> Result<()> {
+    ensure_available("gocryptfs")?;
     println!("Initializing gocryptfs...");
 
     let config_path = repo_dir.join("gocryptfs.conf");
@@ -19,6 +27,15 @@ pub fn init_repository(repo_dir: &Path, objects_dir: &Path, passphrase_file: &Pa
         "--config",
         config_str,
     ]);
+    match cipher {
+        Cipher::AesGcm => {}
+        Cipher::AesSiv => {
+            cmd.arg("-aessiv");
+        }
+        Cipher::Xchacha => {
+            cmd.arg("-xchacha");
+        }
+    }
     cmd.arg("-extpass");
     cmd.arg(format!(
         "gpg --decrypt \"{}\"",
@@ -39,7 +56,10 @@ pub fn mount_repository(
     mount_point: &Path,
     options: Option<&str>,
     repo_dir: &Path,
+    foreground: bool,
+    logfile: Option<&Path>,
 ) -> Result<()> {
+    ensure_available("gocryptfs")?;
     println!("Mounting gocryptfs...");
 
     let config_path = repo_dir.join("gocryptfs.conf");
@@ -59,20 +79,73 @@ pub fn mount_repository(
         cmd.arg("-o").arg(opts);
     }
 
+    if foreground {
+        cmd.arg("-fg");
+    }
+
     cmd.arg(cipher_dir);
     cmd.arg(mount_point);
 
-    run_with_output(&mut cmd).context("gocryptfs mount failed")?;
+    if foreground {
+        println!(
+            "Starting gocryptfs in foreground: '{}' at '{}'...",
+            cipher_dir.display(),
+            mount_point.display()
+        );
+        run_foreground(&mut cmd, logfile)?;
+    } else {
+        run_with_output(&mut cmd).context("gocryptfs mount failed")?;
+        println!(
+            "Mounted '{}' at '{}'",
+            cipher_dir.display(),
+            mount_point.display()
+        );
+    }
+
+    Ok(())
+}
+
+/// Spawn `cmd`, inheriting stdio (or redirecting to `logfile`), and block
+/// until the gocryptfs process exits. Used for `-fg` mounts, where gocryptfs
+/// stays attached to the terminal instead of daemonizing until the repository
+/// is unmounted; a capturing `.output()` call would hide all of its output
+/// for that whole time.
+fn run_foreground(cmd: &mut Command, logfile: Option<&Path>) -> Result<()> {
+    if let Some(path) = logfile {
+        let file = open_logfile(path)?;
+        cmd.stdout(
+            file.try_clone()
+                .context("failed to duplicate logfile handle")?,
+        );
+        cmd.stderr(file);
+    } else {
+        cmd.stdout(Stdio::inherit());
+        cmd.stderr(Stdio::inherit());
+    }
+    cmd.stdin(Stdio::inherit());
+
+    let status = cmd
+        .status()
+        .context("failed to run gocryptfs in foreground")?;
+    if !status.success() {
+        bail!("gocryptfs exited with status {status}");
+    }
 
-    println!(
-        "Mounted '{}' at '{}'",
-        cipher_dir.display(),
-        mount_point.display()
-    );
     Ok(())
 }
 
+fn open_logfile(path: &Path) -> Result<File> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .with_context(|| format!("failed to open logfile '{}'", path.display()))?;
+    set_secret_mode(path)?;
+    Ok(file)
+}
+
 pub fn umount_repository(mount_point: &Path) -> Result<()> {
+    ensure_available("fusermount")?;
     println!("Unmounting '{}'...", mount_point.display());
 
     let mount_str = mount_point
@@ -3,17 +3,20 @@ mod linux;
 #[cfg(target_os = "windows")]
 mod windows;
 
-#[cfg(target_os = "windows")]
-use crate::process::run_with_output;
-use anyhow::{bail, Context, Result};
+use crate::cli::Cipher;
+use crate::mount_table;
+use crate::process::{ensure_available, run_with_output};
+use anyhow::{anyhow, bail, Context, Result};
+use clap::ValueEnum;
 use std::env;
 use std::fs;
+use std::io::Write;
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 
-pub fn create(user: &str, repo_dir: &Path) -> Result<()> {
-    if user.trim().is_empty() {
-        bail!("GPG user/email is required (-u/--user)");
+pub fn create(recipients: &[String], repo_dir: &Path, cipher: Cipher) -> Result<()> {
+    if recipients.is_empty() || recipients.iter().any(|user| user.trim().is_empty()) {
+        bail!("at least one GPG user/email is required (-u/--user)");
     }
 
     let repo_dir = absolute_path(repo_dir)?;
@@ -32,23 +35,42 @@ pub fn create(user: &str, repo_dir: &Path) -> Result<()> {
     set_dir_mode(&objects_dir)?;
 
     let passphrase_file = repo_dir.join("passphrase.gpg");
-    generate_encrypted_passphrase(user, &passphrase_file)?;
+    generate_encrypted_passphrase(recipients, &passphrase_file)?;
     set_secret_mode(&passphrase_file)?;
 
     #[cfg(target_os = "windows")]
     {
-        windows::init_repository(&repo_dir, &objects_dir, &passphrase_file)?;
+        windows::init_repository(&repo_dir, &objects_dir, &passphrase_file, cipher)?;
     }
 
     #[cfg(not(target_os = "windows"))]
     {
-        linux::init_repository(&repo_dir, &objects_dir, &passphrase_file)?;
+        linux::init_repository(&repo_dir, &objects_dir, &passphrase_file, cipher)?;
     }
 
+    write_cipher_record(&repo_dir, cipher)?;
+
     Ok(())
 }
 
-pub fn mount(repo_dir: &Path, mount_point: &Path, options: Option<&str>) -> Result<()> {
+/// Print the list of ciphers supported by `create --cipher`.
+pub fn show_ciphers() {
+    for cipher in Cipher::value_variants() {
+        println!("{:<8} {}", cipher.name(), cipher.description());
+    }
+}
+
+pub fn mount(
+    repo_dir: &Path,
+    mount_point: &Path,
+    options: Option<&str>,
+    foreground: bool,
+    logfile: Option<&Path>,
+) -> Result<()> {
+    if logfile.is_some() && !foreground {
+        bail!("--logfile requires -f/--foreground (the backend only stays attached to redirect in that mode)");
+    }
+
     let repo_dir = absolute_path(repo_dir)?;
     let passphrase_file = repo_dir.join("passphrase.gpg");
     let cipher_dir = repo_dir.join("objects");
@@ -60,22 +82,54 @@ pub fn mount(repo_dir: &Path, mount_point: &Path, options: Option<&str>) -> Resu
         );
     }
 
+    if mount_table::is_source_mounted(&cipher_dir)? {
+        bail!("'{}' is already mounted", cipher_dir.display());
+    }
+    if mount_table::is_target_mounted(mount_point)? {
+        bail!("mount point '{}' is already in use", mount_point.display());
+    }
+
+    let cipher = read_cipher_record(&repo_dir)?;
+    validate_cipher_record(&repo_dir, cipher)?;
+    println!("Repository uses the {} cipher", cipher.name());
+
     ensure_mount_point_exists(mount_point)?;
 
     #[cfg(target_os = "windows")]
     {
-        windows::mount_repository(&cipher_dir, &passphrase_file, mount_point, options, &repo_dir)?;
+        windows::mount_repository(
+            &cipher_dir,
+            &passphrase_file,
+            mount_point,
+            options,
+            &repo_dir,
+            foreground,
+            logfile,
+        )?;
     }
 
     #[cfg(not(target_os = "windows"))]
     {
-        linux::mount_repository(&cipher_dir, &passphrase_file, mount_point, options, &repo_dir)?;
+        linux::mount_repository(
+            &cipher_dir,
+            &passphrase_file,
+            mount_point,
+            options,
+            &repo_dir,
+            foreground,
+            logfile,
+        )?;
     }
 
     Ok(())
 }
 
 pub fn umount(mount_point: &Path) -> Result<()> {
+    if !mount_table::is_target_mounted(mount_point)? {
+        println!("'{}' is not mounted, nothing to do", mount_point.display());
+        return Ok(());
+    }
+
     #[cfg(target_os = "windows")]
     {
         windows::umount_repository(mount_point)?;
@@ -89,6 +143,26 @@ pub fn umount(mount_point: &Path) -> Result<()> {
     Ok(())
 }
 
+pub fn status(repo_dir: &Path) -> Result<()> {
+    let repo_dir = absolute_path(repo_dir)?;
+    let cipher_dir = repo_dir.join("objects");
+
+    let mounted = mount_table::all_mounts()?
+        .into_iter()
+        .find(|entry| entry.source == cipher_dir);
+
+    match mounted {
+        Some(entry) => println!(
+            "'{}' is mounted at '{}'",
+            repo_dir.display(),
+            entry.target.display()
+        ),
+        None => println!("'{}' is not mounted", repo_dir.display()),
+    }
+
+    Ok(())
+}
+
 pub fn normalize_mount_point(input: &str) -> Result<PathBuf> {
     let candidate = PathBuf::from(input);
 
@@ -143,6 +217,59 @@ fn ensure_mount_point_exists(mount_point: &Path) -> Result<()> {
     Ok(())
 }
 
+fn write_cipher_record(repo_dir: &Path, cipher: Cipher) -> Result<()> {
+    let path = repo_dir.join("cipher");
+    fs::write(&path, cipher.name())
+        .with_context(|| format!("failed to record cipher in '{}'", path.display()))
+}
+
+fn read_cipher_record(repo_dir: &Path) -> Result<Cipher> {
+    let path = repo_dir.join("cipher");
+    if !path.is_file() {
+        // Repositories created before cipher selection existed default to AES-GCM.
+        return Ok(Cipher::AesGcm);
+    }
+
+    let contents = fs::read_to_string(&path)
+        .with_context(|| format!("failed to read '{}'", path.display()))?;
+    let name = contents.trim();
+
+    Cipher::from_str(name, true).map_err(|_| {
+        anyhow!(
+            "repository records unknown cipher '{name}' in '{}'",
+            path.display()
+        )
+    })
+}
+
+/// Cross-check the recorded cipher against the feature flags gocryptfs
+/// itself wrote into `gocryptfs.conf`, so a hand-edited or stale `cipher`
+/// file is caught instead of silently trusted.
+fn validate_cipher_record(repo_dir: &Path, cipher: Cipher) -> Result<()> {
+    let config_path = repo_dir.join("gocryptfs.conf");
+    let config = fs::read_to_string(&config_path)
+        .with_context(|| format!("failed to read '{}'", config_path.display()))?;
+
+    let is_siv = config.contains("\"AESSIV\"");
+    let is_xchacha = config.contains("\"XChaCha20Poly1305\"");
+
+    let matches = match cipher {
+        Cipher::AesGcm => !is_siv && !is_xchacha,
+        Cipher::AesSiv => is_siv,
+        Cipher::Xchacha => is_xchacha,
+    };
+
+    if !matches {
+        bail!(
+            "recorded cipher '{}' does not match the feature flags in '{}'; the repository's cipher record is stale or was tampered with",
+            cipher.name(),
+            config_path.display()
+        );
+    }
+
+    Ok(())
+}
+
 fn absolute_path(path: &Path) -> Result<PathBuf> {
     if path.is_absolute() {
         Ok(path.to_path_buf())
@@ -151,7 +278,8 @@ fn absolute_path(path: &Path) -> Result<PathBuf> {
     }
 }
 
-fn generate_encrypted_passphrase(user: &str, passphrase_file: &Path) -> Result<()> {
+fn generate_encrypted_passphrase(recipients: &[String], passphrase_file: &Path) -> Result<()> {
+    ensure_available("gpg")?;
     println!("Generating and encrypting passphrase with GPG...");
 
     let mut random_child = Command::new("gpg")
@@ -165,11 +293,16 @@ fn generate_encrypted_passphrase(user: &str, passphrase_file: &Path) -> Result<(
         .take()
         .context("failed to capture gpg --gen-random stdout")?;
 
-    let encrypt_child = Command::new("gpg")
-        .args(["--encrypt", "--sign", "-r", user, "-o"])
-        .arg(passphrase_file)
-        .stdin(Stdio::from(random_stdout))
-        .stderr(Stdio::piped())
+    let mut encrypt_cmd = Command::new("gpg");
+    encrypt_cmd.args(["--encrypt", "--sign"]);
+    for recipient in recipients {
+        encrypt_cmd.arg("-r").arg(recipient);
+    }
+    encrypt_cmd.arg("-o").arg(passphrase_file);
+    encrypt_cmd.stdin(Stdio::from(random_stdout));
+    encrypt_cmd.stderr(Stdio::piped());
+
+    let encrypt_child = encrypt_cmd
         .spawn()
         .context("failed to start gpg --encrypt pipeline")?;
 
@@ -192,8 +325,8 @@ fn generate_encrypted_passphrase(user: &str, passphrase_file: &Path) -> Result<(
     Ok(())
 }
 
-#[cfg(target_os = "windows")]
 fn decrypt_passphrase(passphrase_file: &Path) -> Result<String> {
+    ensure_available("gpg")?;
     let mut cmd = Command::new("gpg");
     cmd.arg("--decrypt").arg(passphrase_file);
     let output = run_with_output(&mut cmd)?;
@@ -201,6 +334,74 @@ fn decrypt_passphrase(passphrase_file: &Path) -> Result<String> {
     Ok(passphrase)
 }
 
+pub fn rekey(repo_dir: &Path, recipients: &[String]) -> Result<()> {
+    if recipients.is_empty() || recipients.iter().any(|r| r.trim().is_empty()) {
+        bail!("at least one recipient is required (-r/--recipient)");
+    }
+
+    let repo_dir = absolute_path(repo_dir)?;
+    let passphrase_file = repo_dir.join("passphrase.gpg");
+    if !passphrase_file.is_file() {
+        bail!(
+            "repository layout is invalid under '{}': expected passphrase.gpg",
+            repo_dir.display()
+        );
+    }
+
+    println!("Decrypting existing passphrase...");
+    let passphrase = decrypt_passphrase(&passphrase_file)?;
+
+    let tmp_file = repo_dir.join("passphrase.gpg.tmp");
+    println!("Re-encrypting passphrase for new recipients...");
+    let result = encrypt_passphrase(&passphrase, recipients, &tmp_file)
+        .and_then(|()| set_secret_mode(&tmp_file))
+        .and_then(|()| {
+            fs::rename(&tmp_file, &passphrase_file)
+                .with_context(|| format!("failed to replace '{}'", passphrase_file.display()))
+        });
+
+    if result.is_err() {
+        let _ = fs::remove_file(&tmp_file);
+    }
+    result?;
+
+    println!(
+        "Repository re-keyed for {} recipient(s); the master key is unchanged",
+        recipients.len()
+    );
+    Ok(())
+}
+
+fn encrypt_passphrase(passphrase: &str, recipients: &[String], output: &Path) -> Result<()> {
+    ensure_available("gpg")?;
+    let mut cmd = Command::new("gpg");
+    cmd.arg("--encrypt").arg("--sign");
+    for recipient in recipients {
+        cmd.arg("-r").arg(recipient);
+    }
+    cmd.arg("-o").arg(output);
+    cmd.stdin(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+
+    let mut child = cmd.spawn().context("failed to start gpg --encrypt pipeline")?;
+    if let Some(stdin) = child.stdin.as_mut() {
+        stdin
+            .write_all(passphrase.as_bytes())
+            .context("failed to send passphrase to gpg")?;
+    }
+
+    let output = child
+        .wait_with_output()
+        .context("failed waiting for gpg --encrypt")?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("gpg failed to encrypt passphrase: {}", stderr.trim());
+    }
+
+    Ok(())
+}
+
 #[cfg(unix)]
 fn set_secret_mode(path: &Path) -> Result<()> {
     use std::os::unix::fs::PermissionsExt;
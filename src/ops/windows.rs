@@ -1,12 +1,30 @@
 use super::{decrypt_passphrase, set_secret_mode};
-use crate::process::run_with_output;
+use crate::cli::Cipher;
+use crate::process::{ensure_available, run_with_output};
 use anyhow::{bail, Context, Result};
 use std::fs;
 use std::io::Write;
 use std::path::Path;
 use std::process::{Command, Stdio};
 
-pub fn init_repository(repo_dir: &Path, objects_dir: &Path, passphrase_file: &Path) -> Result<()> {
+pub fn init_repository(
+    repo_dir: &Path,
+    objects_dir: &Path,
+    passphrase_file: &Path,
+    cipher: Cipher,
+) -> Result<()> {
+    ensure_available("cppcryptfsctl.exe")?;
+
+    // cppcryptfs only implements AES-256-GCM and AES-256-SIV; there is no
+    // XChaCha20 mode to select on Windows.
+    let cipher_flag = match cipher {
+        Cipher::AesGcm => None,
+        Cipher::AesSiv => Some("--siv"),
+        Cipher::Xchacha => {
+            bail!("cppcryptfs does not support the xchacha cipher on Windows; use aes-gcm or aes-siv")
+        }
+    };
+
     println!("Initializing cppcryptfs...");
 
     let passphrase = decrypt_passphrase(passphrase_file)?;
@@ -19,6 +37,9 @@ pub fn init_repository(repo_dir: &Path, objects_dir: &Path, passphrase_file: &Pa
     cmd.arg(format!("--init={}", objects_dir.display()));
     cmd.arg(format!("--volumename={}", volume_name));
     cmd.arg("--deterministicnames");
+    if let Some(flag) = cipher_flag {
+        cmd.arg(flag);
+    }
     cmd.stdin(Stdio::piped());
     cmd.stderr(Stdio::piped());
 
@@ -53,7 +74,10 @@ pub fn mount_repository(
     mount_point: &Path,
     options: Option<&str>,
     repo_dir: &Path,
+    foreground: bool,
+    logfile: Option<&Path>,
 ) -> Result<()> {
+    ensure_available("cppcryptfs.exe")?;
     println!("Decrypting passphrase...");
     let passphrase = decrypt_passphrase(passphrase_file)?;
 
@@ -75,6 +99,15 @@ pub fn mount_repository(
     if let Some(opts) = options {
         println!("Note: mount options are ignored on Windows (received: {opts})");
     }
+    if foreground {
+        println!("Note: --foreground is ignored on Windows; cppcryptfs always runs as a background process");
+    }
+    if let Some(path) = logfile {
+        println!(
+            "Note: --logfile is ignored on Windows (received: {})",
+            path.display()
+        );
+    }
 
     run_with_output(&mut cmd).context("cppcryptfs.exe mount failed")?;
 
@@ -87,6 +120,7 @@ pub fn mount_repository(
 }
 
 pub fn umount_repository(mount_point: &Path) -> Result<()> {
+    ensure_available("cppcryptfs.exe")?;
     println!("Unmounting '{}'...", mount_point.display());
 
     let mount_str = mount_point
@@ -1,7 +1,51 @@
 use anyhow::{bail, Context, Result};
+use std::env;
 use std::ffi::OsStr;
+use std::path::PathBuf;
 use std::process::{Command, Output};
 
+/// Check that `program` resolves on `PATH`, bailing with a message naming
+/// the missing dependency and a hint for installing it if it does not.
+///
+/// Most operations in this crate shell out to an external binary
+/// (gocryptfs, cppcryptfs, fusermount, gpg); without this check, a missing
+/// one surfaces as a raw `failed to run` OS error instead of something a
+/// user can act on.
+pub fn ensure_available(program: &str) -> Result<()> {
+    if resolve_on_path(program).is_some() {
+        return Ok(());
+    }
+
+    bail!(
+        "required dependency `{program}` was not found on PATH; install {} and try again",
+        install_hint(program)
+    );
+}
+
+fn resolve_on_path(program: &str) -> Option<PathBuf> {
+    let path = env::var_os("PATH")?;
+    env::split_paths(&path).find_map(|dir| {
+        let candidate = dir.join(program);
+        #[cfg(target_os = "windows")]
+        let candidate = if candidate.extension().is_none() {
+            candidate.with_extension("exe")
+        } else {
+            candidate
+        };
+        candidate.is_file().then_some(candidate)
+    })
+}
+
+fn install_hint(program: &str) -> &'static str {
+    match program {
+        "gocryptfs" => "gocryptfs and fuse",
+        "cppcryptfsctl.exe" | "cppcryptfs.exe" => "cppcryptfs",
+        "fusermount" => "fuse",
+        "gpg" => "GnuPG (gpg)",
+        _ => "the missing dependency",
+    }
+}
+
 pub fn format_command(cmd: &Command) -> String {
     let program = cmd.get_program().to_string_lossy();
     let args = cmd